@@ -90,6 +90,25 @@ export class ImageProcessor {
     return this.processor.get_data();
   }
 
+  /**
+   * Get per-pixel luminance normalized to 0..1
+   */
+  getLuma() {
+    return this.processor.luma_f32();
+  }
+
+  /**
+   * Get bounding box of content that differs from the background color
+   */
+  getContentBounds(bg = [255, 255, 255, 255], tolerance = 0) {
+    const bounds = this.processor.content_bounds(new Uint8Array(bg), tolerance);
+    if (bounds.length === 0) {
+      return null;
+    }
+    const [x, y, width, height] = bounds;
+    return { x, y, width, height };
+  }
+
   /**
    * Get image as ImageData for canvas
    */
@@ -139,6 +158,14 @@ export class ImageProcessor {
     return this;
   }
 
+  /**
+   * Apply edge-preserving surface blur
+   */
+  surfaceBlur(radius = 5, threshold = 15) {
+    this.processor.surface_blur(radius, threshold);
+    return this;
+  }
+
   /**
    * Apply sharpen filter
    */
@@ -155,6 +182,22 @@ export class ImageProcessor {
     return this;
   }
 
+  /**
+   * One-click enhance: denoise flat regions, sharpen detailed ones
+   */
+  autoEnhance() {
+    this.processor.auto_enhance();
+    return this;
+  }
+
+  /**
+   * Smooth jagged edges (FXAA-style)
+   */
+  antialias() {
+    this.processor.antialias();
+    return this;
+  }
+
   /**
    * Apply sepia tone
    */
@@ -225,6 +268,53 @@ export class ImageProcessor {
     return this;
   }
 
+  // ===== Channel Operations =====
+
+  /**
+   * Split into four grayscale processors for the R, G, B and A channels
+   */
+  splitChannels() {
+    return this.processor.split_channels().map(channel => {
+      const pixels = channel.get_data();
+      channel.free();
+      return new ImageProcessor(pixels, this.width, this.height);
+    });
+  }
+
+  /**
+   * Recombine four channel processors into one RGBA image
+   */
+  static mergeChannels(r, g, b, a) {
+    const merged = WasmImageProcessor.merge_channels(
+      r.processor,
+      g.processor,
+      b.processor,
+      a.processor
+    );
+    const pixels = merged.get_data();
+    merged.free();
+    return new ImageProcessor(pixels, r.width, r.height);
+  }
+
+  // ===== Encoding =====
+
+  /**
+   * Run-length encode the RGBA data for compact transfer
+   */
+  rleEncode() {
+    return this.processor.rle_encode();
+  }
+
+  /**
+   * Create an ImageProcessor from run-length encoded data
+   */
+  static rleDecode(data, width, height) {
+    const decoded = WasmImageProcessor.rle_decode(data, width, height);
+    const pixels = decoded.get_data();
+    decoded.free();
+    return new ImageProcessor(pixels, width, height);
+  }
+
   // ===== Convenience Methods =====
 
   /**
@@ -1,5 +1,5 @@
 /// Color space filter operations
-use crate::utils::{clamp, blur_gaussian};
+use crate::utils::{clamp, blur_gaussian, local_variance, luma};
 
 /// Convert image to grayscale using luminosity method
 pub fn grayscale(data: &mut [u8]) {
@@ -43,6 +43,138 @@ pub fn sharpen(data: &mut [u8], width: u32, height: u32, amount: f32) {
     }
 }
 
+/// Edge-preserving surface blur using separable horizontal and vertical passes
+///
+/// Each neighbor's weight falls off linearly with distance out to `radius`
+/// and with its per-channel difference from the center out to `threshold`,
+/// so differences at or beyond the threshold do not contribute at all.
+pub fn surface_blur(data: &mut [u8], width: u32, height: u32, radius: f32, threshold: f32) {
+    let width = width as usize;
+    let height = height as usize;
+    let reach = radius.ceil() as i32;
+
+    let weight = |center: u8, neighbor: u8, offset: i32| -> f32 {
+        let spatial = 1.0 - offset.abs() as f32 / (radius + 1.0);
+        let diff = (neighbor as f32 - center as f32).abs();
+        let range = 1.0 - diff / threshold;
+        spatial * range.max(0.0)
+    };
+
+    // Horizontal pass
+    let mut temp = data.to_vec();
+    for y in 0..height {
+        for x in 0..width {
+            for c in 0..3 {
+                let center = data[(y * width + x) * 4 + c];
+                let mut sum = 0.0;
+                let mut weight_sum = 0.0;
+
+                for k in -reach..=reach {
+                    let kx = x as i32 + k;
+                    if kx >= 0 && kx < width as i32 {
+                        let value = data[(y * width + kx as usize) * 4 + c];
+                        let w = weight(center, value, k);
+                        sum += value as f32 * w;
+                        weight_sum += w;
+                    }
+                }
+
+                temp[(y * width + x) * 4 + c] = (sum / weight_sum).round() as u8;
+            }
+        }
+    }
+
+    // Vertical pass
+    for y in 0..height {
+        for x in 0..width {
+            for c in 0..3 {
+                let center = temp[(y * width + x) * 4 + c];
+                let mut sum = 0.0;
+                let mut weight_sum = 0.0;
+
+                for k in -reach..=reach {
+                    let ky = y as i32 + k;
+                    if ky >= 0 && ky < height as i32 {
+                        let value = temp[(ky as usize * width + x) * 4 + c];
+                        let w = weight(center, value, k);
+                        sum += value as f32 * w;
+                        weight_sum += w;
+                    }
+                }
+
+                data[(y * width + x) * 4 + c] = (sum / weight_sum).round() as u8;
+            }
+        }
+    }
+}
+
+/// Lightweight FXAA-style anti-aliasing
+///
+/// Pixels whose local luminance contrast is below the edge threshold are left
+/// untouched. Edge pixels are blended toward the neighbor across the edge, by
+/// an amount that grows with how much they stand out from their neighbors.
+pub fn antialias(data: &mut [u8], width: u32, height: u32) {
+    let width = width as usize;
+    let height = height as usize;
+    let edge_threshold = 0.125;
+    let edge_threshold_min: f32 = 8.0;
+    let subpixel_quality = 0.75;
+
+    let lumas: Vec<f32> = (0..width * height).map(|p| luma(data, p * 4)).collect();
+    let source = data.to_vec();
+
+    for y in 1..height.saturating_sub(1) {
+        for x in 1..width.saturating_sub(1) {
+            let p = y * width + x;
+            let center = lumas[p];
+            let north = lumas[p - width];
+            let south = lumas[p + width];
+            let west = lumas[p - 1];
+            let east = lumas[p + 1];
+
+            let luma_max = center.max(north).max(south).max(west).max(east);
+            let luma_min = center.min(north).min(south).min(west).min(east);
+            let range = luma_max - luma_min;
+            if range < edge_threshold_min.max(luma_max * edge_threshold) {
+                continue;
+            }
+
+            // Blend across the edge, toward the side with the steeper gradient
+            let horizontal = (north + south - 2.0 * center).abs();
+            let vertical = (west + east - 2.0 * center).abs();
+            let neighbor = if horizontal >= vertical {
+                if (north - center).abs() >= (south - center).abs() {
+                    p - width
+                } else {
+                    p + width
+                }
+            } else if (west - center).abs() >= (east - center).abs() {
+                p - 1
+            } else {
+                p + 1
+            };
+
+            // Neighborhood average weights direct neighbors twice the diagonals
+            let diagonals = lumas[p - width - 1]
+                + lumas[p - width + 1]
+                + lumas[p + width - 1]
+                + lumas[p + width + 1];
+            let average = (2.0 * (north + south + west + east) + diagonals) / 12.0;
+            let t = clamp((average - center).abs() / range, 0.0, 1.0);
+            let blend = t * t * (3.0 - 2.0 * t);
+            let blend = blend * blend * subpixel_quality;
+
+            let i = p * 4;
+            let n = neighbor * 4;
+            for c in 0..3 {
+                let original = source[i + c] as f32;
+                let value = original + (source[n + c] as f32 - original) * blend;
+                data[i + c] = clamp(value.round(), 0.0, 255.0) as u8;
+            }
+        }
+    }
+}
+
 /// Sobel edge detection
 pub fn edge_detect(data: &mut [u8], width: u32, height: u32) {
     let width = width as usize;
@@ -87,3 +219,146 @@ pub fn edge_detect(data: &mut [u8], width: u32, height: u32) {
 
     data.copy_from_slice(&output);
 }
+
+/// Denoise flat regions and sharpen detailed ones in a single pass
+///
+/// A 5x5 luminance standard deviation below `flat` selects the blurred pixel,
+/// above `detail` the unsharp-masked pixel, with a smooth blend in between.
+pub fn auto_enhance(data: &mut [u8], width: u32, height: u32) {
+    let flat = 4.0;
+    let detail = 16.0;
+    let amount = 1.0;
+
+    let variance = local_variance(data, width, height, 2);
+    let mut blurred = data.to_vec();
+    blur_gaussian(&mut blurred, width, height, 2.0);
+
+    for (p, var) in variance.iter().enumerate() {
+        let t = clamp((var.sqrt() - flat) / (detail - flat), 0.0, 1.0);
+        let mask = t * t * (3.0 - 2.0 * t);
+        let i = p * 4;
+
+        for c in 0..3 {
+            let original = data[i + c] as f32;
+            let blurred_val = blurred[i + c] as f32;
+            let sharpened = original + (original - blurred_val) * amount;
+            let value = blurred_val + (sharpened - blurred_val) * mask;
+            data[i + c] = clamp(value.round(), 0.0, 255.0) as u8;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gray RGBA image with a vertical step edge at `width / 2`, plus a
+    /// checkerboard of `texture` added on top of both sides
+    fn step_image(width: usize, height: usize, left: u8, right: u8, texture: u8) -> Vec<u8> {
+        let mut data = vec![255u8; width * height * 4];
+        for y in 0..height {
+            for x in 0..width {
+                let base = if x < width / 2 { left } else { right };
+                let value = if (x + y) % 2 == 0 { base + texture } else { base };
+                let idx = (y * width + x) * 4;
+                data[idx..idx + 3].fill(value);
+            }
+        }
+        data
+    }
+
+    fn red(data: &[u8], width: usize, x: usize, y: usize) -> f32 {
+        data[(y * width + x) * 4] as f32
+    }
+
+    fn variance(values: &[f32]) -> f32 {
+        let mean = values.iter().sum::<f32>() / values.len() as f32;
+        values.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / values.len() as f32
+    }
+
+    #[test]
+    fn auto_enhance_smooths_flat_regions_and_sharpens_edges() {
+        let (width, height) = (20, 10);
+        let original = step_image(width, height, 100, 180, 2);
+        let mut data = original.clone();
+
+        auto_enhance(&mut data, width as u32, height as u32);
+
+        // Flat interior well away from the edge
+        let flat = |d: &[u8]| -> Vec<f32> {
+            (3..7)
+                .flat_map(|y| (2..6).map(move |x| (x, y)))
+                .map(|(x, y)| red(d, width, x, y))
+                .collect()
+        };
+        assert!(variance(&flat(&data)) < variance(&flat(&original)));
+
+        let edge = |d: &[u8]| red(d, width, 10, 5) - red(d, width, 9, 5);
+        assert!(edge(&data) > edge(&original));
+    }
+
+    #[test]
+    fn surface_blur_preserves_step_edge_unlike_gaussian() {
+        let (width, height) = (20, 10);
+        let original = step_image(width, height, 60, 180, 6);
+        let mut surface = original.clone();
+        let mut gaussian = original.clone();
+
+        surface_blur(&mut surface, width as u32, height as u32, 3.0, 20.0);
+        blur_gaussian(&mut gaussian, width as u32, height as u32, 3.0);
+
+        // Gentle texture on either side is smoothed
+        let texture = |d: &[u8]| -> Vec<f32> { (2..8).map(|x| red(d, width, x, 5)).collect() };
+        assert!(variance(&texture(&surface)) < variance(&texture(&original)) / 4.0);
+
+        // The step survives surface blur but is smeared by Gaussian blur
+        let edge = |d: &[u8]| red(d, width, 10, 5) - red(d, width, 9, 5);
+        assert!(edge(&surface) > 110.0, "surface edge {}", edge(&surface));
+        assert!(edge(&gaussian) < 60.0, "gaussian edge {}", edge(&gaussian));
+    }
+
+    #[test]
+    fn antialias_smooths_staircase_edge_and_leaves_flat_regions() {
+        let (width, height) = (12, 12);
+        let mut original = vec![255u8; width * height * 4];
+        for y in 0..height {
+            for x in 0..(y / 2) * 2 {
+                let idx = (y * width + x) * 4;
+                original[idx..idx + 3].fill(0);
+            }
+        }
+        let mut data = original.clone();
+
+        antialias(&mut data, width as u32, height as u32);
+
+        // Distance in pixels to the nearest pixel of the opposite color
+        let distance = |x: usize, y: usize| -> usize {
+            let here = red(&original, width, x, y);
+            (0..height)
+                .flat_map(|ny| (0..width).map(move |nx| (nx, ny)))
+                .filter(|&(nx, ny)| red(&original, width, nx, ny) != here)
+                .map(|(nx, ny)| x.abs_diff(nx).max(y.abs_diff(ny)))
+                .min()
+                .unwrap_or(usize::MAX)
+        };
+
+        let mut smoothed = 0;
+        for y in 1..height - 1 {
+            for x in 1..width - 1 {
+                let idx = (y * width + x) * 4;
+                let here = red(&original, width, x, y);
+                let on_edge = [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+                    .iter()
+                    .any(|&(nx, ny)| red(&original, width, nx, ny) != here);
+
+                if on_edge {
+                    assert!(data[idx] > 0 && data[idx] < 255, "edge pixel ({x}, {y})");
+                    smoothed += 1;
+                } else if distance(x, y) >= 2 {
+                    assert_eq!(data[idx..idx + 4], original[idx..idx + 4], "flat pixel ({x}, {y})");
+                }
+            }
+        }
+        assert!(smoothed > 0);
+    }
+}
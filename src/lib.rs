@@ -6,9 +6,10 @@ pub mod transforms;
 pub mod effects;
 pub mod utils;
 
-use filters::{grayscale, blur, sharpen, edge_detect};
+use filters::{grayscale, blur, sharpen, edge_detect, auto_enhance, surface_blur, antialias};
 use transforms::{resize, rotate, flip_horizontal, flip_vertical};
 use effects::{sepia, invert, brightness, contrast};
+use utils::{clamp, luma, content_bounds, rle_encode, rle_decode};
 
 // Thread-local image buffer for efficiency
 thread_local! {
@@ -54,6 +55,29 @@ impl ImageProcessor {
         self.height
     }
 
+    /// Get per-pixel luminance normalized to 0..1
+    pub fn luma_f32(&self) -> Vec<f32> {
+        (0..self.data.len())
+            .step_by(4)
+            .map(|i| clamp(luma(&self.data, i) / 255.0, 0.0, 1.0))
+            .collect()
+    }
+
+    /// Get the [x, y, width, height] box around pixels that differ from the
+    /// RGBA background by more than tolerance (0-255); empty if none do
+    pub fn content_bounds(&self, bg: &[u8], tolerance: f32) -> Result<Vec<u32>, JsValue> {
+        if bg.len() != 4 {
+            return Err(JsValue::from_str("Background must be 4 RGBA values"));
+        }
+        if !(0.0..=255.0).contains(&tolerance) {
+            return Err(JsValue::from_str("Tolerance must be between 0 and 255"));
+        }
+
+        Ok(content_bounds(&self.data, self.width, self.height, bg, tolerance)
+            .map(|(x, y, w, h)| vec![x, y, w, h])
+            .unwrap_or_default())
+    }
+
     /// Apply grayscale filter
     pub fn grayscale(&mut self) -> Result<(), JsValue> {
         grayscale(&mut self.data);
@@ -69,6 +93,18 @@ impl ImageProcessor {
         Ok(())
     }
 
+    /// Apply edge-preserving surface blur with radius and threshold
+    pub fn surface_blur(&mut self, radius: f32, threshold: f32) -> Result<(), JsValue> {
+        if radius <= 0.0 || radius > 50.0 {
+            return Err(JsValue::from_str("Radius must be between 0 and 50"));
+        }
+        if threshold <= 0.0 || threshold > 255.0 {
+            return Err(JsValue::from_str("Threshold must be between 0 and 255"));
+        }
+        surface_blur(&mut self.data, self.width, self.height, radius, threshold);
+        Ok(())
+    }
+
     /// Apply sharpen filter
     pub fn sharpen(&mut self, amount: f32) -> Result<(), JsValue> {
         if amount < 0.0 || amount > 5.0 {
@@ -84,6 +120,18 @@ impl ImageProcessor {
         Ok(())
     }
 
+    /// Smooth flat regions and sharpen detailed regions based on local variance
+    pub fn auto_enhance(&mut self) -> Result<(), JsValue> {
+        auto_enhance(&mut self.data, self.width, self.height);
+        Ok(())
+    }
+
+    /// Smooth jagged edges with FXAA-style anti-aliasing
+    pub fn antialias(&mut self) -> Result<(), JsValue> {
+        antialias(&mut self.data, self.width, self.height);
+        Ok(())
+    }
+
     /// Apply sepia tone
     pub fn sepia(&mut self) -> Result<(), JsValue> {
         sepia(&mut self.data);
@@ -148,9 +196,133 @@ impl ImageProcessor {
         self.height = temp;
         Ok(())
     }
+
+    /// Split into four grayscale processors holding the R, G, B and A channels
+    pub fn split_channels(&self) -> Vec<ImageProcessor> {
+        (0..4)
+            .map(|c| {
+                let mut data = vec![255u8; self.data.len()];
+                for i in (0..self.data.len()).step_by(4) {
+                    let value = self.data[i + c];
+                    data[i] = value;
+                    data[i + 1] = value;
+                    data[i + 2] = value;
+                }
+
+                ImageProcessor {
+                    width: self.width,
+                    height: self.height,
+                    data,
+                }
+            })
+            .collect()
+    }
+
+    /// Recombine four grayscale channel processors into one RGBA image
+    pub fn merge_channels(
+        r: &ImageProcessor,
+        g: &ImageProcessor,
+        b: &ImageProcessor,
+        a: &ImageProcessor,
+    ) -> Result<ImageProcessor, JsValue> {
+        ImageProcessor::try_merge_channels([r, g, b, a]).map_err(JsValue::from_str)
+    }
+
+    /// Run-length encode the RGBA buffer as (count, r, g, b, a) records
+    pub fn rle_encode(&self) -> Vec<u8> {
+        rle_encode(&self.data)
+    }
+
+    /// Create an image processor from run-length encoded RGBA data
+    pub fn rle_decode(data: &[u8], width: u32, height: u32) -> Result<ImageProcessor, JsValue> {
+        let decoded = width
+            .checked_mul(height)
+            .and_then(|pixels| rle_decode(data, pixels as usize))
+            .ok_or_else(|| JsValue::from_str("Invalid run-length data for image dimensions"))?;
+
+        Ok(ImageProcessor {
+            width,
+            height,
+            data: decoded,
+        })
+    }
+}
+
+impl ImageProcessor {
+    /// Merge R, G, B and A channel processors, failing on mismatched dimensions
+    fn try_merge_channels(channels: [&ImageProcessor; 4]) -> Result<ImageProcessor, &'static str> {
+        let (width, height) = (channels[0].width, channels[0].height);
+        if channels
+            .iter()
+            .any(|channel| channel.width != width || channel.height != height)
+        {
+            return Err("All channels must have the same dimensions");
+        }
+
+        let mut data = vec![0u8; channels[0].data.len()];
+        for i in (0..data.len()).step_by(4) {
+            for (c, channel) in channels.iter().enumerate() {
+                data[i + c] = channel.data[i];
+            }
+        }
+
+        Ok(ImageProcessor {
+            width,
+            height,
+            data,
+        })
+    }
 }
 
 #[wasm_bindgen]
 pub fn init_panic_hook() {
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn luma_f32_is_normalized_per_pixel() {
+        let data = [0, 0, 0, 255, 255, 255, 255, 255, 128, 128, 128, 255];
+        let processor = ImageProcessor::new(&data, 3, 1).unwrap();
+
+        let luma = processor.luma_f32();
+
+        assert_eq!(luma.len(), 3);
+        assert_eq!(luma[0], 0.0);
+        assert_eq!(luma[1], 1.0);
+        assert!((luma[2] - 128.0 / 255.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn split_then_merge_round_trips() {
+        let data: Vec<u8> = (0..4 * 3 * 4).map(|v| (v * 37 % 256) as u8).collect();
+        let processor = ImageProcessor::new(&data, 4, 3).unwrap();
+
+        let channels = processor.split_channels();
+        assert_eq!(channels.len(), 4);
+        assert_eq!(channels[3].get_data()[..4], [data[3], data[3], data[3], 255]);
+
+        let merged = ImageProcessor::try_merge_channels([
+            &channels[0],
+            &channels[1],
+            &channels[2],
+            &channels[3],
+        ])
+        .unwrap();
+        assert_eq!((merged.width(), merged.height()), (4, 3));
+        assert_eq!(merged.get_data(), data);
+    }
+
+    #[test]
+    fn merge_channels_rejects_mismatched_dimensions() {
+        let channel = ImageProcessor::new(&[0; 4 * 4 * 4], 4, 4).unwrap();
+        let narrow = ImageProcessor::new(&[0; 2 * 8 * 4], 2, 8).unwrap();
+
+        let merged = ImageProcessor::try_merge_channels([&channel, &channel, &narrow, &channel]);
+
+        assert!(merged.is_err());
+    }
+}
@@ -5,6 +5,76 @@ pub fn clamp(value: f32, min: f32, max: f32) -> f32 {
     value.max(min).min(max)
 }
 
+/// Luminance of the RGBA pixel starting at `idx`
+pub fn luma(data: &[u8], idx: usize) -> f32 {
+    0.299 * data[idx] as f32 + 0.587 * data[idx + 1] as f32 + 0.114 * data[idx + 2] as f32
+}
+
+/// Per-pixel luminance variance over a (2 * radius + 1) square window
+pub fn local_variance(data: &[u8], width: u32, height: u32, radius: u32) -> Vec<f32> {
+    let width = width as usize;
+    let height = height as usize;
+    let radius = radius as usize;
+    let mut variance = vec![0.0; width * height];
+
+    for y in 0..height {
+        for x in 0..width {
+            let mut sum = 0.0;
+            let mut sum_sq = 0.0;
+            let mut count = 0.0;
+
+            for ny in y.saturating_sub(radius)..(y + radius + 1).min(height) {
+                for nx in x.saturating_sub(radius)..(x + radius + 1).min(width) {
+                    let value = luma(data, (ny * width + nx) * 4);
+                    sum += value;
+                    sum_sq += value * value;
+                    count += 1.0;
+                }
+            }
+
+            let mean = sum / count;
+            variance[y * width + x] = (sum_sq / count - mean * mean).max(0.0);
+        }
+    }
+
+    variance
+}
+
+/// Tight (x, y, width, height) box around pixels whose channels differ from
+/// `bg` by more than `tolerance`, or None if every pixel matches the background
+pub fn content_bounds(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    bg: &[u8],
+    tolerance: f32,
+) -> Option<(u32, u32, u32, u32)> {
+    let mut min_x = u32::MAX;
+    let mut min_y = u32::MAX;
+    let mut max_x = 0;
+    let mut max_y = 0;
+
+    for y in 0..height {
+        for x in 0..width {
+            let idx = ((y * width + x) * 4) as usize;
+            let is_content = (0..4)
+                .any(|c| (data[idx + c] as f32 - bg[c] as f32).abs() > tolerance);
+
+            if is_content {
+                min_x = min_x.min(x);
+                min_y = min_y.min(y);
+                max_x = max_x.max(x);
+                max_y = max_y.max(y);
+            }
+        }
+    }
+
+    if min_x == u32::MAX {
+        return None;
+    }
+    Some((min_x, min_y, max_x - min_x + 1, max_y - min_y + 1))
+}
+
 /// Generate Gaussian kernel
 fn gaussian_kernel(radius: f32) -> Vec<f32> {
     let kernel_size = ((radius * 2.0).ceil() as usize) | 1; // Ensure odd size
@@ -56,7 +126,7 @@ pub fn blur_gaussian(data: &mut [u8], width: u32, height: u32, radius: f32) {
                 }
 
                 let idx = (y * width + x) * 4 + c;
-                temp[idx] = (sum / weight_sum.max(1.0)) as u8;
+                temp[idx] = (sum / weight_sum.max(f32::EPSILON)) as u8;
             }
             // Copy alpha
             let idx = (y * width + x) * 4 + 3;
@@ -81,7 +151,7 @@ pub fn blur_gaussian(data: &mut [u8], width: u32, height: u32, radius: f32) {
                 }
 
                 let idx = (y * width + x) * 4 + c;
-                data[idx] = (sum / weight_sum.max(1.0)) as u8;
+                data[idx] = (sum / weight_sum.max(f32::EPSILON)) as u8;
             }
             // Copy alpha
             let idx = (y * width + x) * 4 + 3;
@@ -89,3 +159,120 @@ pub fn blur_gaussian(data: &mut [u8], width: u32, height: u32, radius: f32) {
         }
     }
 }
+
+/// Run-length encode RGBA pixels as (count, r, g, b, a) records
+pub fn rle_encode(data: &[u8]) -> Vec<u8> {
+    let mut output = Vec::new();
+    let mut pixels = data.chunks_exact(4);
+
+    let Some(first) = pixels.next() else {
+        return output;
+    };
+    let mut current = first;
+    let mut count: u8 = 1;
+
+    for pixel in pixels {
+        if pixel == current && count < u8::MAX {
+            count += 1;
+        } else {
+            output.push(count);
+            output.extend_from_slice(current);
+            current = pixel;
+            count = 1;
+        }
+    }
+
+    output.push(count);
+    output.extend_from_slice(current);
+    output
+}
+
+/// Decode data produced by `rle_encode`, returning None if it is malformed
+/// or does not expand to exactly `pixel_count` pixels
+pub fn rle_decode(data: &[u8], pixel_count: usize) -> Option<Vec<u8>> {
+    if !data.len().is_multiple_of(5) {
+        return None;
+    }
+
+    // Validate the total run length before allocating anything
+    let mut total = 0usize;
+    for record in data.chunks_exact(5) {
+        let count = record[0] as usize;
+        if count == 0 {
+            return None;
+        }
+        total += count;
+    }
+    if total != pixel_count {
+        return None;
+    }
+
+    let mut output = Vec::with_capacity(total * 4);
+    for record in data.chunks_exact(5) {
+        for _ in 0..record[0] {
+            output.extend_from_slice(&record[1..5]);
+        }
+    }
+
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blur_keeps_flat_image_flat_at_borders() {
+        let mut data = [200u8, 200, 200, 255].repeat(8 * 8);
+
+        blur_gaussian(&mut data, 8, 8, 3.0);
+
+        // Allow for truncation to u8
+        assert!(data.iter().all(|&v| v >= 199), "{:?}", &data[..32]);
+    }
+
+    #[test]
+    fn rle_compresses_flat_region_and_round_trips() {
+        let data = [40u8, 80, 120, 255].repeat(100 * 100);
+
+        let encoded = rle_encode(&data);
+        assert!(encoded.len() <= 200, "encoded to {} bytes", encoded.len());
+
+        assert_eq!(rle_decode(&encoded, 100 * 100), Some(data));
+        assert_eq!(rle_decode(&encoded, 100 * 100 - 1), None);
+        assert_eq!(rle_decode(&encoded, 100 * 100 + 1), None);
+    }
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+    #[test]
+    fn content_bounds_is_tight_around_centered_shape() {
+        let (width, height) = (10, 8);
+        let mut data = WHITE.repeat(10 * 8);
+        for y in 3..5 {
+            for x in 4..7 {
+                let idx = (y * 10 + x) * 4;
+                data[idx..idx + 3].fill(0);
+            }
+        }
+
+        assert_eq!(content_bounds(&data, width, height, &WHITE, 0.0), Some((4, 3, 3, 2)));
+    }
+
+    #[test]
+    fn content_bounds_is_none_for_background_only() {
+        let data = WHITE.repeat(4 * 4);
+
+        assert_eq!(content_bounds(&data, 4, 4, &WHITE, 0.0), None);
+    }
+
+    #[test]
+    fn content_bounds_ignores_pixels_within_tolerance() {
+        let mut data = WHITE.repeat(4 * 4);
+        data[0] = 250;
+        data[(2 * 4 + 2) * 4] = 0;
+
+        assert_eq!(content_bounds(&data, 4, 4, &WHITE, 10.0), Some((2, 2, 1, 1)));
+        assert_eq!(content_bounds(&data, 4, 4, &WHITE, 0.0), Some((0, 0, 3, 3)));
+    }
+}
@@ -12,6 +12,16 @@ export interface ImageDimensions {
   pixels: number;
 }
 
+/**
+ * Rectangle in pixel coordinates
+ */
+export interface Bounds {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
 /**
  * ImageData-compatible interface for different input formats
  */
@@ -61,6 +71,23 @@ export class ImageProcessor {
    */
   getData(): Uint8Array;
 
+  /**
+   * Get per-pixel luminance normalized to 0..1 (one value per pixel)
+   * Useful as input for heatmaps and data-visualization overlays
+   */
+  getLuma(): Float32Array;
+
+  /**
+   * Get the tight bounding box of pixels that differ from the background
+   * Does not modify the image
+   *
+   * @param bg - Background RGBA color (default: opaque white)
+   * @param tolerance - Max channel difference still treated as background (0-255)
+   * @returns Bounding box, or null if the whole image matches the background
+   * @throws {Error} If tolerance is out of bounds
+   */
+  getContentBounds(bg?: [number, number, number, number], tolerance?: number): Bounds | null;
+
   /**
    * Convert to Canvas ImageData object
    */
@@ -108,6 +135,16 @@ export class ImageProcessor {
    */
   blur(radius?: number): this;
 
+  /**
+   * Apply edge-preserving surface blur
+   * Neighbors contribute less with distance and not at all past the threshold
+   *
+   * @param radius - Blur radius in pixels (0-50)
+   * @param threshold - Max channel difference that still contributes (0-255)
+   * @throws {Error} If radius or threshold is out of bounds
+   */
+  surfaceBlur(radius?: number, threshold?: number): this;
+
   /**
    * Apply unsharp masking sharpening
    *
@@ -121,6 +158,18 @@ export class ImageProcessor {
    */
   edgeDetect(): this;
 
+  /**
+   * One-click enhance driven by local variance
+   * Smooths flat (low-variance) regions and sharpens detailed (high-variance) regions
+   */
+  autoEnhance(): this;
+
+  /**
+   * Apply FXAA-style anti-aliasing
+   * Blends only along detected luminance edges, leaving flat regions untouched
+   */
+  antialias(): this;
+
   /**
    * Apply sepia tone effect
    */
@@ -174,6 +223,45 @@ export class ImageProcessor {
    */
   resize(newWidth: number, newHeight: number): this;
 
+  // ===== Channel Operations =====
+
+  /**
+   * Split into four grayscale processors holding the R, G, B and A channels
+   * Each channel value is stored in R, G and B with opaque alpha
+   */
+  splitChannels(): [ImageProcessor, ImageProcessor, ImageProcessor, ImageProcessor];
+
+  /**
+   * Recombine four grayscale channel processors into one RGBA image
+   * Reads each channel value from the red component of its processor
+   *
+   * @throws {Error} If the channels don't all have the same dimensions
+   */
+  static mergeChannels(
+    r: ImageProcessor,
+    g: ImageProcessor,
+    b: ImageProcessor,
+    a: ImageProcessor
+  ): ImageProcessor;
+
+  // ===== Encoding =====
+
+  /**
+   * Run-length encode the RGBA data as (count, r, g, b, a) records
+   * Compresses well after posterizing or on large flat regions
+   */
+  rleEncode(): Uint8Array;
+
+  /**
+   * Create processor from run-length encoded data
+   *
+   * @param data - Output of rleEncode()
+   * @param width - Image width in pixels
+   * @param height - Image height in pixels
+   * @throws {Error} If data is malformed or doesn't match width × height
+   */
+  static rleDecode(data: Uint8Array, width: number, height: number): ImageProcessor;
+
   // ===== Convenience Methods (Instagram-style Filters) =====
 
   /**